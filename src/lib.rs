@@ -16,37 +16,37 @@ mod tests {
         let h2 = 2;
 
         // first item at 0
-        assert_eq!(page.insert(h0, UVec3::splat(6)), Slot::New(UVec3::ZERO));
+        assert_eq!(page.insert(h0, UVec3::splat(6), ()), Slot::New(UVec3::ZERO));
 
         // inserting again gets same
         assert_eq!(
-            page.insert(h0, UVec3::splat(6)),
+            page.insert(h0, UVec3::splat(6), ()),
             Slot::Existing(UVec3::ZERO)
         );
 
         // second item doesn't fit
-        assert_eq!(page.insert(h1, UVec3::splat(6)), Slot::NoFit);
+        assert_eq!(page.insert(h1, UVec3::splat(6), ()), Slot::NoFit);
 
         // smaller item fits right
         assert_eq!(
-            page.insert(h2, UVec3::splat(4)),
+            page.insert(h2, UVec3::splat(4), ()),
             Slot::New(UVec3::new(6, 0, 0))
         );
 
         // second item fits after removal
         page.remove(&h0);
-        assert_eq!(page.insert(h1, UVec3::splat(6)), Slot::New(UVec3::ZERO));
+        assert_eq!(page.insert(h1, UVec3::splat(6), ()), Slot::New(UVec3::ZERO));
 
         // first item no longer fits
-        assert_eq!(page.insert(h0, UVec3::splat(6)), Slot::NoFit);
+        assert_eq!(page.insert(h0, UVec3::splat(6), ()), Slot::NoFit);
 
         let mut page = AtlasPage::new(UVec3::splat(10));
-        page.insert(h0, UVec3::splat(2));
-        let Slot::New(pos) = page.insert(h1, UVec3::splat(2)) else {panic!()};
-        page.insert(h2, UVec3::splat(2));
+        page.insert(h0, UVec3::splat(2), ());
+        let Slot::New(pos) = page.insert(h1, UVec3::splat(2), ()) else {panic!()};
+        page.insert(h2, UVec3::splat(2), ());
         page.remove(&h1);
         // reinsert gets original location if not paged out
-        assert_eq!(page.insert(h1, UVec3::splat(2)), Slot::Existing(pos))
+        assert_eq!(page.insert(h1, UVec3::splat(2), ()), Slot::Existing(pos))
     }
 
     #[test]
@@ -56,186 +56,505 @@ mod tests {
         let h0 = 0;
         let h1 = 1;
 
-        page.insert(h1, UVec3::ONE);
+        page.insert(h1, UVec3::ONE, ());
         page.remove(&h1);
 
-        assert_eq!(page.insert(h0, UVec3::ONE), Slot::New(UVec3::X));
-        assert_eq!(page.insert(h1, UVec3::ONE), Slot::Existing(UVec3::ZERO));
+        assert_eq!(page.insert(h0, UVec3::ONE, ()), Slot::New(UVec3::X));
+        assert_eq!(page.insert(h1, UVec3::ONE, ()), Slot::Existing(UVec3::ZERO));
+    }
+
+    #[test]
+    fn value_and_spatial_lookup() {
+        let mut page = AtlasPage::new(UVec3::splat(10));
+
+        let h0 = 0;
+        let h1 = 1;
+
+        page.insert(h0, UVec3::splat(4), "first");
+        page.insert(h1, UVec3::splat(4), "second");
+
+        assert_eq!(page.get_value(&h0), Some(&"first"));
+        assert_eq!(page.get_value(&h1), Some(&"second"));
+
+        *page.get_value_mut(&h0).unwrap() = "updated";
+        assert_eq!(page.get_value(&h0), Some(&"updated"));
+
+        let (handle, info, value) = page.at(UVec3::new(1, 1, 1)).unwrap();
+        assert_eq!(*handle, h0);
+        assert_eq!(info.position, UVec3::ZERO);
+        assert_eq!(*value, "updated");
+
+        assert!(page.at(UVec3::splat(9)).is_none());
+
+        assert_eq!(page.iter().count(), 2);
+    }
+
+    #[test]
+    fn atlas_spillover_and_compact() {
+        use crate::Atlas;
+
+        let mut atlas = Atlas::new(UVec3::splat(10));
+
+        let h0 = 0;
+        let h1 = 1;
+
+        // fills the first page entirely
+        let loc0 = atlas.insert(h0, UVec3::splat(10), ());
+        assert_eq!(loc0.page, 0);
+        assert_eq!(atlas.page_count(), 1);
+
+        // doesn't fit on page 0, so a second page is allocated
+        let loc1 = atlas.insert(h1, UVec3::splat(10), ());
+        assert_eq!(loc1.page, 1);
+        assert_eq!(atlas.page_count(), 2);
+
+        atlas.purge(&h1);
+        atlas.compact();
+        assert_eq!(atlas.page_count(), 1);
+    }
+
+    #[test]
+    fn atlas_insert_too_big_leaves_no_dangling_location() {
+        use crate::Atlas;
+
+        let mut atlas = Atlas::new(UVec3::splat(10));
+
+        let h0 = 0;
+        let h1 = 1;
+
+        // fills the first page entirely, so there is no room left on it for h1
+        atlas.insert(h0, UVec3::splat(10), ());
+
+        // item is bigger than the whole page dim, so it can never fit anywhere;
+        // a fresh page is tried and discarded rather than kept around empty
+        assert_eq!(atlas.insert(h1, UVec3::splat(20), ()).slot, Slot::NoFit);
+        assert_eq!(atlas.page_count(), 1);
+
+        // a later insert of a handle that never had a home must not panic on a
+        // dangling location index
+        assert_eq!(atlas.insert(h1, UVec3::splat(20), ()).slot, Slot::NoFit);
+    }
+
+    #[test]
+    fn atlas_insert_falls_back_when_known_page_no_longer_fits() {
+        use crate::Atlas;
+
+        let mut atlas = Atlas::new(UVec3::splat(10));
+
+        let h0 = 0;
+        let h1 = 1;
+
+        // h0 lives on page 0 at first
+        let loc0 = atlas.insert(h0, UVec3::splat(4), ());
+        assert_eq!(loc0.page, 0);
+
+        // takes up part of page 0, so an item the size of the whole page no longer fits there
+        atlas.insert(h1, UVec3::splat(6), ());
+
+        // h0 is gone, but its reservation (and `locations` entry) stick around in
+        // case it comes back the same size
+        atlas.remove(&h0);
+
+        // re-inserting h0 at a size that no longer fits on its known page (h1 is
+        // still occupying part of it) must fall through to a new page rather
+        // than returning Slot::NoFit
+        let loc0 = atlas.insert(h0, UVec3::splat(10), ());
+        assert_ne!(loc0.slot, Slot::NoFit);
+        assert_eq!(loc0.page, 1);
+        assert_eq!(atlas.page_count(), 2);
+    }
+
+    #[test]
+    fn repack_defragments_and_reports_moved_items() {
+        let mut page = AtlasPage::new(UVec3::new(9, 1, 1));
+
+        let h0 = 0; // size 4, at x=0
+        let h1 = 1; // size 2, at x=4 (removed below)
+        let h2 = 2; // size 3, at x=6
+
+        page.insert(h0, UVec3::new(4, 1, 1), ());
+        page.insert(h1, UVec3::new(2, 1, 1), ());
+        page.insert(h2, UVec3::new(3, 1, 1), ());
+
+        // removing the middle item leaves a dead reservation and fragments the
+        // free list, even though h2 could slide down to close the gap
+        page.remove(&h1);
+        assert_eq!(page.dead_volume(), 2);
+
+        let h2_before = page.get(&h2).unwrap().position;
+        assert_eq!(h2_before, UVec3::new(6, 0, 0));
+
+        let moved = page.repack().expect("all live items still fit in the same volume");
+
+        // the dead reservation is gone and h2 was packed down into the gap
+        assert_eq!(page.dead_volume(), 0);
+        assert_eq!(moved.get(&h2), Some(&UVec3::new(4, 0, 0)));
+        assert_eq!(moved.get(&h0), None);
+        assert_eq!(page.get(&h0).unwrap().position, UVec3::ZERO);
+    }
+
+    #[test]
+    fn repack_restores_state_when_greedy_packing_fails() {
+        // a largest-first re-pack of these items into the same (7, 6, 1) page
+        // they already legally occupy has no solution: greedy placement paints
+        // itself into a corner even though the original (non-greedy) layout
+        // below proves the items do all fit together
+        let dim = UVec3::new(7, 6, 1);
+        let mut page: AtlasPage<i32, ()> = AtlasPage::new(dim);
+
+        let items = [
+            (0, UVec3::new(3, 3, 1), UVec3::new(4, 3, 0)),
+            (1, UVec3::new(3, 4, 1), UVec3::new(1, 1, 0)),
+            (2, UVec3::new(2, 3, 1), UVec3::new(5, 0, 0)),
+            (3, UVec3::new(4, 1, 1), UVec3::new(0, 0, 0)),
+            (4, UVec3::new(2, 1, 1), UVec3::new(1, 5, 0)),
+            (5, UVec3::new(1, 1, 1), UVec3::new(4, 0, 0)),
+            (6, UVec3::new(1, 3, 1), UVec3::new(0, 3, 0)),
+        ];
+
+        for &(handle, size, position) in &items {
+            page.live_items.insert(handle, crate::AtlasInfo { size, position, value: () });
+        }
+        // the free list doesn't matter for repack (it's rebuilt from scratch), but
+        // keep it consistent with "every reservation above is occupied"
+        page.free_boxes = Vec::new();
+
+        let before = page.live_items.clone();
+
+        assert_eq!(page.repack(), None);
+
+        // a failed repack must leave the page exactly as it was, not half-packed
+        assert_eq!(page.live_items, before);
+    }
+
+    #[test]
+    fn placement_heuristics_pick_different_candidates() {
+        use crate::PlacementHeuristic;
+
+        let dim = UVec3::splat(100);
+        let size = UVec3::splat(10);
+
+        // three candidate free boxes, each the clear winner under exactly one
+        // heuristic: `a` has the smallest single leftover axis (best short side),
+        // `b` has the smallest largest leftover axis (best long side), and `c`
+        // has the smallest leftover volume (best volume fit)
+        let a = (UVec3::new(90, 90, 0), UVec3::new(10, 10, 100));
+        let b = (UVec3::new(60, 60, 60), UVec3::new(15, 15, 15));
+        let c = (UVec3::ZERO, UVec3::new(10, 10, 10));
+
+        let cases = [
+            (PlacementHeuristic::BestShortSideFit, a.0),
+            (PlacementHeuristic::BestLongSideFit, b.0),
+            (PlacementHeuristic::BestVolumeFit, c.0),
+        ];
+
+        for (heuristic, expected_pos) in cases {
+            let mut page: AtlasPage<i32, ()> = AtlasPage::new(dim).with_heuristic(heuristic);
+            page.free_boxes = vec![a, b, c];
+            assert_eq!(page.insert(0, size, ()), Slot::New(expected_pos));
+        }
+    }
+
+    #[test]
+    fn best_volume_fit_does_not_overflow_on_large_pages() {
+        use crate::PlacementHeuristic;
+
+        let mut page: AtlasPage<i32, ()> =
+            AtlasPage::new(UVec3::splat(2000)).with_heuristic(PlacementHeuristic::BestVolumeFit);
+
+        assert_eq!(
+            page.insert(0, UVec3::splat(100), ()),
+            Slot::New(UVec3::ZERO)
+        );
+    }
+
+    #[test]
+    fn free_list_stays_bounded_under_insert_remove_churn() {
+        let mut page = AtlasPage::new(UVec3::splat(64));
+        let h = 0;
+        let size = UVec3::splat(8);
+
+        for _ in 0..500 {
+            page.insert(h, size, ());
+            page.purge(&h);
+        }
+
+        // each purge hands the item's box back to the free list; the item first
+        // walks along x to the far side of the page, then keeps landing in the same
+        // exact-fit box there, which leaves no remnants, so the free list stops
+        // growing rather than growing without bound
+        assert!(
+            page.free_boxes.len() < 20,
+            "free list grew to {} boxes after churn",
+            page.free_boxes.len()
+        );
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct AtlasHandle(usize);
 
-#[derive(Clone, Copy, Debug)]
-pub struct AtlasInfo {
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AtlasInfo<V> {
     pub size: UVec3,
     pub position: UVec3,
+    pub value: V,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Slot {
     NoFit,
     New(UVec3),
     Existing(UVec3),
 }
 
+// how to break ties between free boxes that are all big enough to hold an item
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PlacementHeuristic {
+    // prefer the placement that leaves the smallest leftover extent
+    #[default]
+    BestShortSideFit,
+    // prefer the placement whose largest leftover extent is smallest
+    BestLongSideFit,
+    // prefer the free box closest in volume to the item being placed
+    BestVolumeFit,
+}
+
 #[derive(Clone)]
-pub struct AtlasPage<H: Eq + Hash + Clone> {
+pub struct AtlasPage<H: Eq + Hash + Clone, V> {
     pub dim: UVec3,
-    live_items: HashMap<H, AtlasInfo>,
-    dead_items: HashMap<H, AtlasInfo>,
+    live_items: HashMap<H, AtlasInfo<V>>,
+    dead_items: HashMap<H, AtlasInfo<V>>,
+    // free volume, tracked as a (possibly overlapping) list of boxes rather than
+    // re-derived from item corners on every insert
+    free_boxes: Vec<(UVec3, UVec3)>,
+    heuristic: PlacementHeuristic,
 }
 
-impl<H: Eq + Hash + Clone> AtlasPage<H> {
+impl<H: Eq + Hash + Clone, V> AtlasPage<H, V> {
     pub fn new(dim: UVec3) -> Self {
         Self {
             dim,
             live_items: Default::default(),
             dead_items: Default::default(),
+            free_boxes: vec![(UVec3::ZERO, dim)],
+            heuristic: Default::default(),
         }
     }
 
-    fn measure(&self, pos: UVec3, size: UVec3) -> Option<(u32, Vec<H>)> {
-        // check if we fit within the page
-        if (pos + size).cmpgt(self.dim).any() {
-            return None;
-        }
-
-        let new_lhs = pos;
-        let new_rhs = pos + size;
-
-        let mut distance = self.dim - pos - size;
-        let mut to_clear = Vec::new();
+    // use `heuristic` to break ties between otherwise-equal placements
+    pub fn with_heuristic(mut self, heuristic: PlacementHeuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
 
-        // check for intersections with live items
-        for current_item in self.live_items.values() {
-            let cur_lhs = current_item.position;
-            let cur_rhs = current_item.position + current_item.size;
+    fn intersects(pos_a: UVec3, size_a: UVec3, pos_b: UVec3, size_b: UVec3) -> bool {
+        pos_a.cmplt(pos_b + size_b).all() && pos_b.cmplt(pos_a + size_a).all()
+    }
 
-            let intersects = new_lhs.cmplt(cur_rhs) & new_rhs.cmpgt(cur_lhs);
+    // dead items whose reserved box overlaps a candidate placement
+    fn evicted_by(&self, pos: UVec3, size: UVec3) -> Vec<H> {
+        self.dead_items
+            .iter()
+            .filter(|(_, info)| Self::intersects(pos, size, info.position, info.size))
+            .map(|(handle, _)| handle.clone())
+            .collect()
+    }
 
-            if intersects.all() {
-                return None;
+    // the portion of a free box left over once `opos`/`osize` is carved out of it,
+    // as up to six slabs (two per axis: the part strictly below and strictly above
+    // the occupied region, each clamped to the free box's other two extents)
+    fn split_free_box(fpos: UVec3, fsize: UVec3, opos: UVec3, osize: UVec3) -> Vec<(UVec3, UVec3)> {
+        let f_max = fpos + fsize;
+        let o_max = opos + osize;
+        let mut remnants = Vec::with_capacity(6);
+
+        for axis in 0..3 {
+            if opos[axis] > fpos[axis] {
+                let mut size = fsize;
+                size[axis] = opos[axis] - fpos[axis];
+                remnants.push((fpos, size));
             }
-
-            if intersects.y && intersects.z && cur_lhs.x > new_rhs.x {
-                let distance_x = cur_lhs.x - new_rhs.x;
-                if distance_x < distance.x {
-                    distance.x = distance_x;
-                }
+            if o_max[axis] < f_max[axis] {
+                let mut pos = fpos;
+                pos[axis] = o_max[axis];
+                let mut size = fsize;
+                size[axis] = f_max[axis] - o_max[axis];
+                remnants.push((pos, size));
             }
+        }
 
-            if intersects.x && intersects.z && cur_lhs.y > new_rhs.y {
-                let distance_y = cur_lhs.y - new_rhs.y;
-                if distance_y < distance.y {
-                    distance.y = distance_y;
-                }
-            }
+        remnants
+    }
 
-            if intersects.x && intersects.y && cur_lhs.z > new_rhs.z {
-                let distance_z = cur_lhs.z - new_rhs.z;
-                if distance_z < distance.z {
-                    distance.z = distance_z;
-                }
+    // carve `size` @ `pos` out of the free list, splitting any free box it overlaps
+    // into its leftover slabs, then drop free boxes that are now redundant
+    fn occupy(&mut self, pos: UVec3, size: UVec3) {
+        let mut kept = Vec::with_capacity(self.free_boxes.len());
+        let mut remnants = Vec::new();
+
+        for (fpos, fsize) in self.free_boxes.drain(..) {
+            if Self::intersects(fpos, fsize, pos, size) {
+                remnants.extend(Self::split_free_box(fpos, fsize, pos, size));
+            } else {
+                kept.push((fpos, fsize));
             }
         }
 
-        // check for intersections with dead items
-        for (dead_handle, dead_item) in self.dead_items.iter() {
-            let cur_lhs = dead_item.position;
-            let cur_rhs = dead_item.position + dead_item.size;
+        kept.extend(remnants);
+        self.free_boxes = kept;
+        self.prune_contained();
+    }
 
-            let intersects = new_lhs.cmplt(cur_rhs) & new_rhs.cmpgt(cur_lhs);
+    // drop any free box that is fully contained within another, to stop the free
+    // list growing without bound
+    fn prune_contained(&mut self) {
+        let boxes = std::mem::take(&mut self.free_boxes);
+        self.free_boxes = boxes
+            .iter()
+            .enumerate()
+            .filter(|&(i, &(pos, size))| {
+                !boxes.iter().enumerate().any(|(j, &(other_pos, other_size))| {
+                    j != i
+                        && other_pos.cmple(pos).all()
+                        && (pos + size).cmple(other_pos + other_size).all()
+                        && (other_size.cmpgt(size).any() || j < i)
+                })
+            })
+            .map(|(_, &b)| b)
+            .collect();
+    }
 
-            if intersects.all() {
-                to_clear.push(dead_handle.clone());
+    // tie-break key for a candidate placement, ascending = better, shaped by `self.heuristic`
+    fn fit_key(&self, free_pos: UVec3, free_size: UVec3, size: UVec3) -> (u64, u64, u64) {
+        match self.heuristic {
+            PlacementHeuristic::BestShortSideFit | PlacementHeuristic::BestLongSideFit => {
+                let leftover = self.dim - free_pos - size;
+                let mut sorted = [leftover.x as u64, leftover.y as u64, leftover.z as u64];
+                sorted.sort_unstable();
+                if self.heuristic == PlacementHeuristic::BestShortSideFit {
+                    (sorted[0], sorted[1], sorted[2])
+                } else {
+                    (sorted[2], sorted[1], sorted[0])
+                }
+            }
+            PlacementHeuristic::BestVolumeFit => {
+                // widened to u64 (same as `used_volume`/`dead_volume`/`free_volume`) since
+                // large dims can overflow a `u32` product here
+                (Self::volume(free_size) - Self::volume(size), 0, 0)
             }
         }
-
-        Some((distance.x + distance.y + distance.z, to_clear))
     }
 
-    pub fn insert(&mut self, handle: H, size: UVec3) -> Slot {
-        if let Some(info) = self.live_items.get(&handle) {
+    pub fn insert(&mut self, handle: H, size: UVec3, value: V) -> Slot {
+        if let Some(info) = self.live_items.get_mut(&handle) {
             assert_eq!(size, info.size);
+            info.value = value;
             return Slot::Existing(info.position);
         }
 
-        if let Some(info) = self.dead_items.remove(&handle) {
+        if let Some(mut info) = self.dead_items.remove(&handle) {
             if size == info.size {
-                // back from the dead
+                // back from the dead: reclaim its reserved space at the same position
+                info.value = value;
+                let evictions = self.evicted_by(info.position, size);
+                self.occupy(info.position, size);
+                for evicted in evictions {
+                    self.dead_items.remove(&evicted);
+                }
+                let position = info.position;
                 self.live_items.insert(handle, info);
-                return Slot::Existing(info.position);
+                return Slot::Existing(position);
+            }
+            // size changed: drop the stale reservation and fall through to a fresh placement
+        }
+
+        let mut best: Option<(UVec3, usize, (u64, u64, u64))> = None;
+
+        for &(free_pos, free_size) in &self.free_boxes {
+            if free_size.cmplt(size).any() {
+                continue;
             }
 
-            // otherwise remove from dead and carry on
-        }
-
-        let (mut best_point, mut best_distance, mut best_evict_count, mut evictions) =
-            (None, u32::MAX, usize::MAX, Vec::new());
-
-        let mut insert_points = vec![UVec3::ZERO];
-        for item in self.live_items.values() {
-            insert_points.extend([
-                item.position + item.size * UVec3::X,
-                item.position + item.size * UVec3::Y,
-                item.position + item.size * UVec3::Z,
-            ]);
-        }
-        for item in self.dead_items.values() {
-            insert_points.extend([
-                item.position + item.size * UVec3::X,
-                item.position + item.size * UVec3::Y,
-                item.position + item.size * UVec3::Z,
-            ]);
-        }
-
-        for insert_point in insert_points {
-            if let Some((insert_distance, insert_evictions)) = self.measure(insert_point, size) {
-                if insert_evictions.len() < best_evict_count
-                    || insert_evictions.len() == best_evict_count && insert_distance < best_distance
-                {
-                    best_point = Some(insert_point);
-                    best_distance = insert_distance;
-                    best_evict_count = insert_evictions.len();
-                    evictions = insert_evictions;
+            let evict_count = self.evicted_by(free_pos, size).len();
+            let fit = self.fit_key(free_pos, free_size, size);
+
+            let better = match best {
+                None => true,
+                Some((_, best_evict, best_fit)) => {
+                    evict_count < best_evict || (evict_count == best_evict && fit < best_fit)
                 }
+            };
+
+            if better {
+                best = Some((free_pos, evict_count, fit));
             }
         }
 
-        match best_point {
-            Some(position) => {
-                self.live_items.insert(handle, AtlasInfo { size, position });
-                for item in evictions {
-                    self.dead_items.remove(&item);
+        match best {
+            Some((position, ..)) => {
+                let evictions = self.evicted_by(position, size);
+                self.occupy(position, size);
+                for evicted in evictions {
+                    self.dead_items.remove(&evicted);
                 }
-
+                self.live_items.insert(handle, AtlasInfo { size, position, value });
                 Slot::New(position)
             }
             None => Slot::NoFit,
         }
     }
 
-    pub fn get(&self, handle: &H) -> Option<AtlasInfo> {
-        self.live_items.get(handle).copied()
+    pub fn get(&self, handle: &H) -> Option<&AtlasInfo<V>> {
+        self.live_items.get(handle)
+    }
+
+    pub fn get_value(&self, handle: &H) -> Option<&V> {
+        self.live_items.get(handle).map(|info| &info.value)
+    }
+
+    pub fn get_value_mut(&mut self, handle: &H) -> Option<&mut V> {
+        self.live_items.get_mut(handle).map(|info| &mut info.value)
+    }
+
+    // the live item whose box contains `point`, if any
+    pub fn at(&self, point: UVec3) -> Option<(&H, &AtlasInfo<V>, &V)> {
+        self.live_items
+            .iter()
+            .find(|(_, info)| info.position.cmple(point).all() && point.cmplt(info.position + info.size).all())
+            .map(|(handle, info)| (handle, info, &info.value))
+    }
+
+    // iterate all live items as (handle, placement info, value)
+    pub fn iter(&self) -> impl Iterator<Item = (&H, &AtlasInfo<V>, &V)> {
+        self.live_items.iter().map(|(handle, info)| (handle, info, &info.value))
     }
 
-    // mark as dead, keep around in case it gets added back
+    // mark as dead, keep around in case it gets added back, and release its
+    // space back to the free list so other items can use it in the meantime
     pub fn remove(&mut self, handle: &H) {
         if let Some((key, info)) = self.live_items.remove_entry(handle) {
+            self.free_boxes.push((info.position, info.size));
+            self.prune_contained();
             self.dead_items.insert(key, info);
         }
     }
 
     // remove without keeping in reserve
     pub fn purge(&mut self, handle: &H) {
-        self.live_items.remove(handle);
+        if let Some(info) = self.live_items.remove(handle) {
+            self.free_boxes.push((info.position, info.size));
+            self.prune_contained();
+        }
         self.dead_items.remove(handle);
     }
 
     // mark all handles as dead
     pub fn remove_all(&mut self) {
+        for info in self.live_items.values() {
+            self.free_boxes.push((info.position, info.size));
+        }
+        self.prune_contained();
         self.dead_items.extend(self.live_items.drain())
     }
 
@@ -243,5 +562,196 @@ impl<H: Eq + Hash + Clone> AtlasPage<H> {
     pub fn purge_all(&mut self) {
         self.live_items.clear();
         self.dead_items.clear();
+        self.free_boxes = vec![(UVec3::ZERO, self.dim)];
+    }
+
+    fn volume(size: UVec3) -> u64 {
+        size.x as u64 * size.y as u64 * size.z as u64
+    }
+
+    // volume currently occupied by live items
+    pub fn used_volume(&self) -> u64 {
+        self.live_items.values().map(|info| Self::volume(info.size)).sum()
+    }
+
+    // volume reserved by dead items still available for "back from the dead" reuse
+    pub fn dead_volume(&self) -> u64 {
+        self.dead_items.values().map(|info| Self::volume(info.size)).sum()
+    }
+
+    // volume not occupied by a live item
+    pub fn free_volume(&self) -> u64 {
+        Self::volume(self.dim).saturating_sub(self.used_volume())
+    }
+
+    // fraction of the page occupied by live items, in [0, 1]
+    pub fn occupancy(&self) -> f32 {
+        let total = Self::volume(self.dim);
+        if total == 0 {
+            return 0.0;
+        }
+        self.used_volume() as f32 / total as f32
+    }
+}
+
+impl<H: Eq + Hash + Clone, V: Clone> AtlasPage<H, V> {
+    // defragment by dropping all dead items and re-inserting every live item
+    // largest-first into a fresh empty layout; on success returns the handles whose
+    // position changed, so the caller can update GPU buffers. greedy packing
+    // isn't guaranteed to succeed even into the same volume it came from, so on
+    // failure the page is left exactly as it was and `None` is returned
+    pub fn repack(&mut self) -> Option<HashMap<H, UVec3>> {
+        let original_live = self.live_items.clone();
+        let original_dead = self.dead_items.clone();
+        let original_free_boxes = self.free_boxes.clone();
+
+        let mut items: Vec<(H, AtlasInfo<V>)> = std::mem::take(&mut self.live_items).into_iter().collect();
+        items.sort_by_key(|(_, info)| std::cmp::Reverse(Self::volume(info.size)));
+
+        self.dead_items.clear();
+        self.free_boxes = vec![(UVec3::ZERO, self.dim)];
+
+        let mut moved = HashMap::new();
+        for (handle, info) in items {
+            let old_position = info.position;
+            match self.insert(handle.clone(), info.size, info.value) {
+                Slot::New(position) if position != old_position => {
+                    moved.insert(handle, position);
+                }
+                Slot::New(_) | Slot::Existing(_) => {}
+                Slot::NoFit => {
+                    // greedy largest-first packing doesn't always find a solution even
+                    // though one exists (general bin-packing is hard); restore the
+                    // original layout rather than leaving the page half-repacked
+                    self.live_items = original_live;
+                    self.dead_items = original_dead;
+                    self.free_boxes = original_free_boxes;
+                    return None;
+                }
+            }
+        }
+
+        Some(moved)
+    }
+}
+
+// which page `Atlas::insert` tries first when more than one could fit an item
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PagePolicy {
+    // spread load by trying the emptiest page first
+    #[default]
+    PreferEmptiest,
+    // pack tightly by trying the fullest page first
+    PreferFullest,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AtlasLocation {
+    pub page: usize,
+    pub slot: Slot,
+}
+
+// a growable collection of same-sized `AtlasPage`s: insert spills over into a new
+// page once every existing one reports `Slot::NoFit`
+pub struct Atlas<H: Eq + Hash + Clone, V> {
+    dim: UVec3,
+    pages: Vec<AtlasPage<H, V>>,
+    locations: HashMap<H, usize>,
+    policy: PagePolicy,
+}
+
+impl<H: Eq + Hash + Clone, V> Atlas<H, V> {
+    pub fn new(dim: UVec3) -> Self {
+        Self {
+            dim,
+            pages: Vec::new(),
+            locations: Default::default(),
+            policy: Default::default(),
+        }
+    }
+
+    // use `policy` to choose between existing pages before creating a new one
+    pub fn with_policy(mut self, policy: PagePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn page(&self, index: usize) -> Option<&AtlasPage<H, V>> {
+        self.pages.get(index)
+    }
+
+    // mark as dead on the page that owns `handle`, if any
+    pub fn remove(&mut self, handle: &H) {
+        if let Some(&page) = self.locations.get(handle) {
+            self.pages[page].remove(handle);
+        }
+    }
+
+    // remove without keeping in reserve, forgetting which page owned it
+    pub fn purge(&mut self, handle: &H) {
+        if let Some(page) = self.locations.remove(handle) {
+            self.pages[page].purge(handle);
+        }
+    }
+
+    // drop now-empty trailing pages
+    pub fn compact(&mut self) {
+        while matches!(self.pages.last(), Some(page) if page.used_volume() == 0 && page.dead_volume() == 0)
+        {
+            self.pages.pop();
+        }
+    }
+
+    // page indices ordered by `self.policy` for this insert attempt
+    fn page_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.pages.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (self.pages[a].occupancy(), self.pages[b].occupancy());
+            match self.policy {
+                PagePolicy::PreferEmptiest => a.total_cmp(&b),
+                PagePolicy::PreferFullest => b.total_cmp(&a),
+            }
+        });
+        order
+    }
+}
+
+impl<H: Eq + Hash + Clone, V: Clone> Atlas<H, V> {
+    // try each existing page in turn (ordered by `self.policy`), then allocate a
+    // new page of `dim` if none of them fit
+    pub fn insert(&mut self, handle: H, size: UVec3, value: V) -> AtlasLocation {
+        if let Some(&page) = self.locations.get(&handle) {
+            let slot = self.pages[page].insert(handle.clone(), size, value.clone());
+            if !matches!(slot, Slot::NoFit) {
+                return AtlasLocation { page, slot };
+            }
+            // no longer fits on its previous page (e.g. it grew after a remove/resize):
+            // forget that mapping and fall through to the normal multi-page search
+            self.locations.remove(&handle);
+        }
+
+        for page in self.page_order() {
+            let slot = self.pages[page].insert(handle.clone(), size, value.clone());
+            if !matches!(slot, Slot::NoFit) {
+                self.locations.insert(handle, page);
+                return AtlasLocation { page, slot };
+            }
+        }
+
+        let page = self.pages.len();
+        self.pages.push(AtlasPage::new(self.dim));
+        let slot = self.pages[page].insert(handle.clone(), size, value);
+        if matches!(slot, Slot::NoFit) {
+            // didn't fit even in a fresh empty page (e.g. bigger than `dim`): don't leave
+            // behind an empty page or a dangling location pointing at it
+            self.pages.pop();
+        } else {
+            self.locations.insert(handle, page);
+        }
+        AtlasLocation { page, slot }
     }
 }